@@ -3,16 +3,23 @@ use tauri::Emitter;
 
 use crate::config::AmberConfig;
 use crate::error::AmberError;
+use crate::search::{self, SearchResult};
 use crate::storage;
 use crate::AppState;
 
 #[derive(Debug, Clone, Serialize)]
 pub struct AppStatus {
-    pub watchers_running: bool,
-    pub buffered_events: usize,
+    pub sources: Vec<SourceStatus>,
     pub last_summarized: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct SourceStatus {
+    pub name: String,
+    pub running: bool,
+    pub buffered_events: usize,
+}
+
 #[tauri::command]
 pub fn get_config() -> Result<AmberConfig, AmberError> {
     crate::config::load_or_default()
@@ -29,16 +36,33 @@ pub async fn get_daily_note(date: String) -> Result<Option<String>, AmberError>
 }
 
 #[tauri::command]
-pub fn get_status(
+pub async fn get_status(
     state: tauri::State<'_, std::sync::Mutex<AppState>>,
 ) -> Result<AppStatus, AmberError> {
-    let state = state
-        .lock()
-        .map_err(|e| AmberError::Config(format!("Lock error: {}", e)))?;
+    let (watchers, last_summarized) = {
+        let state = state
+            .lock()
+            .map_err(|e| AmberError::Config(format!("Lock error: {}", e)))?;
+        (state.watchers.clone(), state.last_summarized.clone())
+    };
+
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let guard = watchers.lock().await;
+
+    let mut sources = Vec::with_capacity(guard.len());
+    for watcher in guard.iter() {
+        let name = watcher.name().to_string();
+        let buffered_events = storage::count_unsummarized(Some(&name), &today).await?;
+        sources.push(SourceStatus {
+            name,
+            running: watcher.is_running(),
+            buffered_events,
+        });
+    }
+
     Ok(AppStatus {
-        watchers_running: state.watchers_running,
-        buffered_events: state.buffered_events,
-        last_summarized: state.last_summarized.clone(),
+        sources,
+        last_summarized,
     })
 }
 
@@ -48,3 +72,58 @@ pub fn trigger_summarize(app: tauri::AppHandle) -> Result<(), AmberError> {
         .map_err(|e| AmberError::Config(format!("Emit error: {}", e)))?;
     Ok(())
 }
+
+#[tauri::command]
+pub async fn search_notes(query: String, top_k: usize) -> Result<Vec<SearchResult>, AmberError> {
+    let config = crate::config::load_or_default()?;
+    search::search_notes(query, top_k, &config.summarizer).await
+}
+
+#[tauri::command]
+pub fn start_event_recording(
+    state: tauri::State<'_, std::sync::Mutex<AppState>>,
+    path: String,
+) -> Result<(), AmberError> {
+    let bus = state
+        .lock()
+        .map_err(|e| AmberError::Config(format!("Lock error: {}", e)))?
+        .event_bus
+        .clone();
+    crate::replay::start_recording(bus, path)
+}
+
+#[tauri::command]
+pub fn stop_event_recording() -> Result<(), AmberError> {
+    crate::replay::stop_recording()
+}
+
+/// Feed a recorded event sequence back through the live pipeline, then
+/// re-run the daily summarizer over `date` so a past note can
+/// be regenerated - after tweaking the prompt or provider config - without
+/// the original repos present.
+#[tauri::command]
+pub async fn replay_events(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, std::sync::Mutex<AppState>>,
+    path: String,
+    date: String,
+    preserve_timing: bool,
+) -> Result<(), AmberError> {
+    let (tx, watchers) = {
+        let state = state
+            .lock()
+            .map_err(|e| AmberError::Config(format!("Lock error: {}", e)))?;
+        (state.event_tx.clone(), state.watchers.clone())
+    };
+
+    crate::replay::replay(&path, tx, preserve_timing).await?;
+
+    // Replayed events are byte-identical to the originals, so they hash to
+    // the same staging keys `clear_staging` already marked summarized on a
+    // prior run - without this, `summarize_day` would read zero events and
+    // the note would never be rewritten.
+    crate::storage::unmark_summarized(&date).await?;
+
+    let config = crate::config::load_or_default()?;
+    crate::summarizer::summarize_day(&date, &config, &watchers, &app).await
+}
@@ -9,11 +9,15 @@ pub struct AmberConfig {
     pub summarizer: SummarizerConfig,
     pub schedule: ScheduleConfig,
     pub storage: StorageConfig,
+    pub notifications: NotificationsConfig,
+    pub streaming: StreamingConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SourcesConfig {
     pub git: GitSourceConfig,
+    pub commands: Vec<CommandSourceConfig>,
+    pub webhook: WebhookSourceConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,12 +27,41 @@ pub struct GitSourceConfig {
     pub enabled: bool,
 }
 
+/// A generic polling source: `command` is run with `args` every
+/// `interval_secs`, and new lines in its stdout (vs. the previous run)
+/// become events labeled with `name`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandSourceConfig {
+    pub name: String,
+    pub command: String,
+    pub args: Vec<String>,
+    pub interval_secs: u64,
+    pub enabled: bool,
+}
+
+/// An HTTP ingestion source: external tools POST a `RawEvent` JSON body to
+/// `bind_addr`, authenticated with `shared_secret`, instead of Amber having
+/// to watch for their activity itself.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookSourceConfig {
+    pub enabled: bool,
+    pub bind_addr: String,
+    pub shared_secret: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SummarizerConfig {
     pub provider: String,
     pub model: String,
     pub api_base: String,
     pub api_key_env: String,
+    pub embeddings: EmbeddingsConfig,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddingsConfig {
+    pub model: String,
+    pub enabled: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -42,6 +75,37 @@ pub struct StorageConfig {
     pub base_dir: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationsConfig {
+    pub enabled: bool,
+    pub on_summary: bool,
+    pub on_commit: bool,
+    pub dedup_window_secs: u64,
+    pub smtp: SmtpConfig,
+}
+
+/// An SMTP sink for notifications, alongside the always-on desktop sink.
+/// Disabled by default since it needs real credentials to do anything.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmtpConfig {
+    pub enabled: bool,
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    pub password_env: String,
+    pub from: String,
+    pub to: String,
+}
+
+/// Local HTTP endpoint that fans every `RawEvent` out as Server-Sent Events,
+/// so a frontend can show a live activity feed instead of waiting for the
+/// next summary.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamingConfig {
+    pub enabled: bool,
+    pub bind_addr: String,
+}
+
 impl Default for AmberConfig {
     fn default() -> Self {
         Self {
@@ -51,12 +115,32 @@ impl Default for AmberConfig {
                     scan_depth: 3,
                     enabled: true,
                 },
+                // Disabled by default - an example of wiring a non-git
+                // source through the same CommandWatcher.
+                commands: vec![CommandSourceConfig {
+                    name: "custom-command".to_string(),
+                    command: "echo".to_string(),
+                    args: vec!["configure sources.commands to poll a command".to_string()],
+                    interval_secs: 300,
+                    enabled: false,
+                }],
+                // Disabled by default - a shared secret must be set before
+                // this endpoint is safe to expose.
+                webhook: WebhookSourceConfig {
+                    enabled: false,
+                    bind_addr: "127.0.0.1:4318".to_string(),
+                    shared_secret: String::new(),
+                },
             },
             summarizer: SummarizerConfig {
                 provider: "openai-compatible".to_string(),
                 model: "gpt-4o-mini".to_string(),
                 api_base: "https://api.openai.com/v1".to_string(),
                 api_key_env: "OPENAI_API_KEY".to_string(),
+                embeddings: EmbeddingsConfig {
+                    model: "text-embedding-3-small".to_string(),
+                    enabled: true,
+                },
             },
             schedule: ScheduleConfig {
                 ingest_minutes: 15,
@@ -65,6 +149,25 @@ impl Default for AmberConfig {
             storage: StorageConfig {
                 base_dir: "~/.amber".to_string(),
             },
+            notifications: NotificationsConfig {
+                enabled: true,
+                on_summary: true,
+                on_commit: false,
+                dedup_window_secs: 300,
+                smtp: SmtpConfig {
+                    enabled: false,
+                    host: "smtp.example.com".to_string(),
+                    port: 587,
+                    username: String::new(),
+                    password_env: "AMBER_SMTP_PASSWORD".to_string(),
+                    from: String::new(),
+                    to: String::new(),
+                },
+            },
+            streaming: StreamingConfig {
+                enabled: true,
+                bind_addr: "127.0.0.1:4317".to_string(),
+            },
         }
     }
 }
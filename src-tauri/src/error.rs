@@ -11,12 +11,16 @@ pub enum AmberError {
     Watcher(String),
     #[error("Provider error: {0}")]
     Provider(String),
+    #[error("Notifier error: {0}")]
+    Notifier(String),
     #[error("JSON error: {0}")]
     Serde(#[from] serde_json::Error),
     #[error("YAML error: {0}")]
     Yaml(#[from] serde_yaml::Error),
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
 }
 
 impl Serialize for AmberError {
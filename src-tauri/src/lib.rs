@@ -1,7 +1,11 @@
 pub mod commands;
 pub mod config;
 pub mod error;
+pub mod notifier;
+pub mod replay;
+pub mod search;
 pub mod storage;
+pub mod stream;
 pub mod summarizer;
 pub mod tray;
 pub mod watchers;
@@ -10,20 +14,31 @@ use std::sync::Mutex;
 
 pub struct AppState {
     pub config: config::AmberConfig,
-    pub watchers_running: bool,
-    pub buffered_events: usize,
     pub last_summarized: Option<String>,
+    pub watchers: watchers::SharedWatchers,
+    pub event_bus: stream::EventBus,
+    pub event_tx: tokio::sync::mpsc::Sender<watchers::RawEvent>,
+    event_rx: Option<tokio::sync::mpsc::Receiver<watchers::RawEvent>>,
 }
 
 impl AppState {
     pub fn new(config: config::AmberConfig) -> Mutex<Self> {
+        let (event_tx, event_rx) = tokio::sync::mpsc::channel(100);
         Mutex::new(Self {
             config,
-            watchers_running: false,
-            buffered_events: 0,
             last_summarized: None,
+            watchers: std::sync::Arc::new(tokio::sync::Mutex::new(Vec::new())),
+            event_bus: stream::new_bus(),
+            event_tx,
+            event_rx: Some(event_rx),
         })
     }
+
+    /// Hand the receiving half to `run_all` on startup. Only ever `Some`
+    /// once - `run_all` runs exactly once per process.
+    pub fn take_event_rx(&mut self) -> Option<tokio::sync::mpsc::Receiver<watchers::RawEvent>> {
+        self.event_rx.take()
+    }
 }
 
 pub fn run() {
@@ -33,6 +48,7 @@ pub fn run() {
 
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_notification::init())
         .manage(AppState::new(config))
         .setup(|app| {
             // Ensure storage directories exist
@@ -59,6 +75,10 @@ pub fn run() {
             commands::get_daily_note,
             commands::get_status,
             commands::trigger_summarize,
+            commands::search_notes,
+            commands::start_event_recording,
+            commands::stop_event_recording,
+            commands::replay_events,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,34 @@
+use async_trait::async_trait;
+use tauri_plugin_notification::NotificationExt;
+
+use crate::error::AmberError;
+use crate::notifier::Notifier;
+
+/// Delivers notifications as native desktop popups via
+/// `tauri-plugin-notification`.
+pub struct DesktopSink {
+    app: tauri::AppHandle,
+}
+
+impl DesktopSink {
+    pub fn new(app: tauri::AppHandle) -> Self {
+        Self { app }
+    }
+}
+
+#[async_trait]
+impl Notifier for DesktopSink {
+    async fn send(&self, title: &str, body: &str) -> Result<(), AmberError> {
+        self.app
+            .notification()
+            .builder()
+            .title(title)
+            .body(body)
+            .show()
+            .map_err(|e| AmberError::Notifier(format!("Desktop notification failed: {}", e)))
+    }
+
+    fn name(&self) -> &str {
+        "desktop"
+    }
+}
@@ -0,0 +1,146 @@
+pub mod desktop;
+pub mod smtp;
+
+use std::hash::{Hash, Hasher};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use log::error;
+
+use crate::config::NotificationsConfig;
+use crate::error::AmberError;
+
+/// A destination a notification can be delivered to - desktop popup, email,
+/// or any future sink. Every call site builds the active set from config
+/// and fires the same title/body at all of them.
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    async fn send(&self, title: &str, body: &str) -> Result<(), AmberError>;
+
+    /// Stable label folded into the dedup fingerprint so a duplicate
+    /// suppressed on one sink doesn't also suppress it on another.
+    fn name(&self) -> &str;
+}
+
+fn fingerprint(parts: &[&str]) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    parts.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Returns true if a notification with this fingerprint was already sent
+/// within the configured dedup window, so the caller should suppress it.
+/// Does not itself record anything - callers must call `mark_sent` once the
+/// send it's guarding actually succeeds. Persisted in the event store
+/// (rather than kept in memory) so suppression survives a restart instead
+/// of re-firing every notification on launch.
+async fn is_duplicate(config: &NotificationsConfig, fingerprint: u64) -> bool {
+    let window = Duration::from_secs(config.dedup_window_secs);
+    match crate::storage::is_duplicate_notification(fingerprint, window).await {
+        Ok(duplicate) => duplicate,
+        Err(e) => {
+            error!("Failed to check notification dedup store: {}", e);
+            false
+        }
+    }
+}
+
+/// Records `fingerprint` as sent, so it's suppressed within the dedup window
+/// from now on. Only call this after a send has actually succeeded - marking
+/// it sooner would suppress a legitimate retry of a failed send.
+async fn mark_sent(fingerprint: u64) {
+    if let Err(e) = crate::storage::record_notification_sent(fingerprint).await {
+        error!("Failed to record notification dedup fingerprint: {}", e);
+    }
+}
+
+/// The sinks active for this config - desktop is always available (gated by
+/// `config.enabled`/`config.on_*` at each call site), SMTP only once
+/// configured.
+fn active_sinks(app: &tauri::AppHandle, config: &NotificationsConfig) -> Vec<Box<dyn Notifier>> {
+    let mut sinks: Vec<Box<dyn Notifier>> = vec![Box::new(desktop::DesktopSink::new(app.clone()))];
+    if config.smtp.enabled {
+        sinks.push(Box::new(smtp::SmtpSink::new(config.smtp.clone())));
+    }
+    sinks
+}
+
+/// Fire `title`/`body` at every active sink, deduping per sink + identifier
+/// (`key_parts`) so one noisy sink can't suppress a notification on another.
+async fn dispatch(
+    app: &tauri::AppHandle,
+    config: &NotificationsConfig,
+    key_parts: &[&str],
+    title: &str,
+    body: &str,
+) {
+    for sink in active_sinks(app, config) {
+        let mut parts = vec![sink.name()];
+        parts.extend_from_slice(key_parts);
+        let fp = fingerprint(&parts);
+        if is_duplicate(config, fp).await {
+            continue;
+        }
+        match sink.send(title, body).await {
+            Ok(()) => mark_sent(fp).await,
+            Err(e) => error!("Notifier '{}' failed: {}", sink.name(), e),
+        }
+    }
+}
+
+/// Notify that `summarize_day` finished writing a daily note.
+pub async fn notify_summary_ready(
+    app: &tauri::AppHandle,
+    config: &NotificationsConfig,
+    date: &str,
+    commit_count: usize,
+    repo_count: usize,
+) {
+    if !config.enabled || !config.on_summary {
+        return;
+    }
+
+    let body = format!(
+        "{} commit{} across {} repo{}",
+        commit_count,
+        if commit_count == 1 { "" } else { "s" },
+        repo_count,
+        if repo_count == 1 { "" } else { "s" },
+    );
+    dispatch(app, config, &["summary", date], "Daily note ready", &body).await;
+}
+
+/// Notify that a watched repo recorded a new commit.
+pub async fn notify_new_commit(
+    app: &tauri::AppHandle,
+    config: &NotificationsConfig,
+    repo: &str,
+    hash: &str,
+    subject: &str,
+) {
+    if !config.enabled || !config.on_commit {
+        return;
+    }
+    dispatch(app, config, &["commit", repo, hash], repo, subject).await;
+}
+
+/// Notify that a watcher hit an error, so a silently-stalled source doesn't
+/// go unnoticed until the next daily note comes up empty.
+pub async fn notify_watcher_error(
+    app: &tauri::AppHandle,
+    config: &NotificationsConfig,
+    source: &str,
+    message: &str,
+) {
+    if !config.enabled {
+        return;
+    }
+    dispatch(
+        app,
+        config,
+        &["watcher_error", source, message],
+        &format!("{} watcher error", source),
+        message,
+    )
+    .await;
+}
@@ -0,0 +1,64 @@
+use async_trait::async_trait;
+use lettre::message::Message;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Tokio1Executor};
+
+use crate::config::SmtpConfig;
+use crate::error::AmberError;
+use crate::notifier::Notifier;
+
+/// Delivers notifications as plain-text emails over SMTP, for alerts that
+/// should reach the user even when Amber isn't running on their desktop.
+pub struct SmtpSink {
+    config: SmtpConfig,
+}
+
+impl SmtpSink {
+    pub fn new(config: SmtpConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl Notifier for SmtpSink {
+    async fn send(&self, title: &str, body: &str) -> Result<(), AmberError> {
+        let password = std::env::var(&self.config.password_env).map_err(|_| {
+            AmberError::Notifier(format!("Missing env var: {}", self.config.password_env))
+        })?;
+
+        let email = Message::builder()
+            .from(
+                self.config
+                    .from
+                    .parse()
+                    .map_err(|e| AmberError::Notifier(format!("Invalid 'from' address: {}", e)))?,
+            )
+            .to(self
+                .config
+                .to
+                .parse()
+                .map_err(|e| AmberError::Notifier(format!("Invalid 'to' address: {}", e)))?)
+            .subject(title)
+            .body(body.to_string())
+            .map_err(|e| AmberError::Notifier(format!("Failed to build email: {}", e)))?;
+
+        let creds = Credentials::new(self.config.username.clone(), password);
+
+        let mailer = AsyncSmtpTransport::<Tokio1Executor>::relay(&self.config.host)
+            .map_err(|e| AmberError::Notifier(format!("Failed to configure SMTP relay: {}", e)))?
+            .port(self.config.port)
+            .credentials(creds)
+            .build();
+
+        mailer
+            .send(email)
+            .await
+            .map_err(|e| AmberError::Notifier(format!("Failed to send email: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "smtp"
+    }
+}
@@ -0,0 +1,158 @@
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::{mpsc, oneshot};
+
+use crate::error::AmberError;
+use crate::stream::EventBus;
+use crate::watchers::RawEvent;
+
+/// A recorded `RawEvent` plus how many milliseconds after recording started
+/// it was observed, so `replay` can optionally reproduce the original
+/// cadence between events instead of firing them all at once.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ReplayEntry {
+    offset_ms: u64,
+    event: RawEvent,
+}
+
+fn recording_handle() -> &'static Mutex<Option<oneshot::Sender<()>>> {
+    static HANDLE: OnceLock<Mutex<Option<oneshot::Sender<()>>>> = OnceLock::new();
+    HANDLE.get_or_init(|| Mutex::new(None))
+}
+
+/// Subscribe to `bus` and append every event it carries to `path` as JSONL
+/// `ReplayEntry` records until `stop_recording` is called. Only one
+/// recording can be in progress at a time - debugging a summary means
+/// capturing one fixed sequence, not juggling several. Events the recorder
+/// misses by lagging behind the bus are logged and skipped.
+pub fn start_recording(bus: EventBus, path: String) -> Result<(), AmberError> {
+    let mut handle = recording_handle().lock().unwrap();
+    if handle.is_some() {
+        return Err(AmberError::Storage(
+            "A recording is already in progress".into(),
+        ));
+    }
+
+    let (shutdown_tx, mut shutdown_rx) = oneshot::channel();
+    *handle = Some(shutdown_tx);
+
+    let mut rx = bus.subscribe();
+    tauri::async_runtime::spawn(async move {
+        let mut file = match tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .await
+        {
+            Ok(file) => file,
+            Err(e) => {
+                error!("Failed to open replay recording file {}: {}", path, e);
+                return;
+            }
+        };
+
+        let started = Instant::now();
+        loop {
+            tokio::select! {
+                _ = &mut shutdown_rx => break,
+                received = rx.recv() => {
+                    let event = match received {
+                        Ok(event) => event,
+                        Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
+                            warn!("Replay recording lagged and missed {} events", n);
+                            continue;
+                        }
+                        Err(_) => break,
+                    };
+                    let entry = ReplayEntry {
+                        offset_ms: started.elapsed().as_millis() as u64,
+                        event,
+                    };
+                    match serde_json::to_string(&entry) {
+                        Ok(line) => {
+                            if let Err(e) = file.write_all(format!("{}\n", line).as_bytes()).await {
+                                error!("Failed to write replay entry: {}", e);
+                            }
+                        }
+                        Err(e) => error!("Failed to serialize replay entry: {}", e),
+                    }
+                }
+            }
+        }
+
+        info!("Stopped recording events to {}", path);
+    });
+
+    info!("Started recording events to {}", path);
+    Ok(())
+}
+
+/// Stop the in-progress recording, if any.
+pub fn stop_recording() -> Result<(), AmberError> {
+    let mut handle = recording_handle().lock().unwrap();
+    match handle.take() {
+        Some(tx) => {
+            let _ = tx.send(());
+            Ok(())
+        }
+        None => Err(AmberError::Storage("No recording is in progress".into())),
+    }
+}
+
+/// Read a recording back and feed each event through `tx` - the same sender
+/// `run_all`'s receive loop listens on - so a replayed batch is staged,
+/// deduped, and notified exactly like a live one, letting
+/// `DailySummarizer::generate` be re-run deterministically without the
+/// original repos present. Returns the number of events replayed.
+pub async fn replay(
+    path: &str,
+    tx: mpsc::Sender<RawEvent>,
+    preserve_timing: bool,
+) -> Result<usize, AmberError> {
+    let content = tokio::fs::read_to_string(path).await?;
+
+    let mut entries = Vec::new();
+    for line in content.lines().filter(|l| !l.is_empty()) {
+        match serde_json::from_str::<ReplayEntry>(line) {
+            Ok(entry) => entries.push(entry),
+            Err(e) => warn!("Skipping invalid replay entry: {}", e),
+        }
+    }
+
+    let mut last_offset = 0;
+    let mut replayed = 0;
+    for entry in &entries {
+        if preserve_timing {
+            let gap = entry.offset_ms.saturating_sub(last_offset);
+            if gap > 0 {
+                tokio::time::sleep(Duration::from_millis(gap)).await;
+            }
+            last_offset = entry.offset_ms;
+        }
+
+        if tx.send(entry.event.clone()).await.is_err() {
+            warn!(
+                "Replay receiver closed early after {} of {} events",
+                replayed,
+                entries.len()
+            );
+            break;
+        }
+        replayed += 1;
+    }
+
+    // Block until `run_all`'s receive loop has drained everything sent
+    // above into storage - without this, a caller that immediately
+    // re-summarizes the day could race the replayed batch still sitting in
+    // the channel, exactly the nondeterminism replay is meant to avoid.
+    if replayed > 0 {
+        crate::watchers::drain_barrier(&tx).await?;
+    }
+
+    info!("Replayed {} events from {}", replayed, path);
+    Ok(replayed)
+}
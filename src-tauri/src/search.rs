@@ -0,0 +1,97 @@
+use serde::Serialize;
+
+use crate::config::SummarizerConfig;
+use crate::error::AmberError;
+use crate::storage;
+use crate::summarizer::provider::{Embedder, OpenAICompatibleEmbedder};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchResult {
+    pub date: String,
+    pub chunk_text: String,
+    pub score: f32,
+}
+
+fn embedder(config: &SummarizerConfig) -> OpenAICompatibleEmbedder {
+    OpenAICompatibleEmbedder {
+        api_base: config.api_base.clone(),
+        model: config.embeddings.model.clone(),
+        api_key_env: config.api_key_env.clone(),
+    }
+}
+
+fn chunk_into_paragraphs(content: &str) -> Vec<String> {
+    content
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+fn normalize(vector: &mut [f32]) {
+    let norm: f32 = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        for value in vector.iter_mut() {
+            *value /= norm;
+        }
+    }
+}
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()
+}
+
+/// Chunk a freshly written daily note into paragraphs, embed each chunk, and
+/// persist the result so `search_notes` can find it later. A no-op if
+/// embeddings are disabled or the note has no paragraphs to chunk.
+pub async fn index_note(date: &str, content: &str, config: &SummarizerConfig) -> Result<(), AmberError> {
+    if !config.embeddings.enabled {
+        return Ok(());
+    }
+
+    let chunks = chunk_into_paragraphs(content);
+    if chunks.is_empty() {
+        return Ok(());
+    }
+
+    let mut vectors = embedder(config).embed(chunks.clone()).await?;
+    for vector in vectors.iter_mut() {
+        normalize(vector);
+    }
+
+    storage::replace_note_chunks(date, &chunks, &vectors).await
+}
+
+/// Embed `query` and return the `top_k` highest cosine-similarity chunks
+/// across all indexed notes, via an exact brute-force scan.
+pub async fn search_notes(
+    query: String,
+    top_k: usize,
+    config: &SummarizerConfig,
+) -> Result<Vec<SearchResult>, AmberError> {
+    if !config.embeddings.enabled {
+        return Err(AmberError::Provider("Embeddings are disabled".into()));
+    }
+
+    let mut query_vector = embedder(config)
+        .embed(vec![query])
+        .await?
+        .pop()
+        .ok_or_else(|| AmberError::Provider("No embedding returned for query".into()))?;
+    normalize(&mut query_vector);
+
+    let mut scored: Vec<SearchResult> = storage::all_note_chunks()
+        .await?
+        .into_iter()
+        .map(|chunk| SearchResult {
+            score: dot(&query_vector, &chunk.vector),
+            date: chunk.date,
+            chunk_text: chunk.chunk_text,
+        })
+        .collect();
+
+    scored.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    scored.truncate(top_k);
+    Ok(scored)
+}
@@ -1,7 +1,30 @@
-use std::path::PathBuf;
-use tokio::io::AsyncWriteExt;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use log::{info, warn};
+use serde_json::Value;
+use sled::Db;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::{Row, SqlitePool};
+use tokio::sync::OnceCell;
 
 use crate::error::AmberError;
+use crate::watchers::{CommitFields, RawEvent};
+
+static POOL: OnceCell<SqlitePool> = OnceCell::const_new();
+static EVENTS_DB: OnceCell<Db> = OnceCell::const_new();
+
+/// Tree holding, for every event key already folded into a daily note, the
+/// timestamp it was summarized at - kept separate from `events` so a note
+/// can be regenerated later without re-ingesting anything.
+const SUMMARIZED_TREE: &str = "summarized";
+
+/// Tree holding the sent-at timestamp of every recently-dispatched
+/// notification fingerprint, keyed by the fingerprint itself, so dedup
+/// suppression (see `notifier::is_duplicate`) survives a restart instead of
+/// resetting with the process.
+const NOTIFICATIONS_TREE: &str = "notification_fingerprints";
 
 fn amber_dir() -> Result<PathBuf, AmberError> {
     let home = dirs::home_dir()
@@ -9,10 +32,207 @@ fn amber_dir() -> Result<PathBuf, AmberError> {
     Ok(home.join(".amber"))
 }
 
+/// A single event, as returned by `query_events`.
+#[derive(Debug, Clone)]
+pub struct EventRecord {
+    pub source: String,
+    pub date: String,
+    pub timestamp: String,
+    pub kind: String,
+    pub data: Value,
+    pub summarized_at: Option<String>,
+}
+
+fn sled_err(e: sled::Error) -> AmberError {
+    AmberError::Storage(format!("Event store error: {}", e))
+}
+
+fn content_hash(bytes: &[u8]) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// `date\0timestamp\0hash` - sorting by key therefore sorts by date, then
+/// timestamp, with the content hash only there to disambiguate same-instant
+/// events and make a re-emitted event (the git watcher can re-fire for a
+/// commit it's already reported) overwrite itself instead of duplicating.
+fn event_key(date: &str, timestamp: &str, event_json: &str) -> Vec<u8> {
+    format!(
+        "{}\0{}\0{:016x}",
+        date,
+        timestamp,
+        content_hash(event_json.as_bytes())
+    )
+    .into_bytes()
+}
+
+fn date_prefix(date: &str) -> Vec<u8> {
+    format!("{}\0", date).into_bytes()
+}
+
+fn key_date(key: &[u8]) -> String {
+    String::from_utf8_lossy(key)
+        .split('\0')
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Parses a raw JSONL buffer line-by-line, the way a crash-safe reader of
+/// an append-only file must: a trailing line with no newline yet (the
+/// writer could have been killed mid-write) is returned unparsed rather
+/// than guessed at, and a line that's invalid UTF-8 or invalid JSON is
+/// logged and skipped rather than aborting the whole read. Returns the
+/// successfully parsed events, any trailing partial bytes, and how many
+/// lines were skipped so the caller knows the batch was lossy.
+fn parse_jsonl_resilient(buf: &[u8]) -> (Vec<RawEvent>, &[u8], usize) {
+    let mut events = Vec::new();
+    let mut skipped = 0;
+    let mut start = 0;
+
+    for (i, &byte) in buf.iter().enumerate() {
+        if byte != b'\n' {
+            continue;
+        }
+        let line = &buf[start..i];
+        start = i + 1;
+        if line.is_empty() {
+            continue;
+        }
+
+        match std::str::from_utf8(line) {
+            Ok(text) => match serde_json::from_str::<RawEvent>(text) {
+                Ok(event) => events.push(event),
+                Err(e) => {
+                    warn!("Skipping invalid staging event line: {}", e);
+                    skipped += 1;
+                }
+            },
+            Err(e) => {
+                warn!("Skipping invalid UTF-8 staging line: {}", e);
+                skipped += 1;
+            }
+        }
+    }
+
+    (events, &buf[start..], skipped)
+}
+
+/// One-time migration for anyone upgrading from the original per-day JSONL
+/// staging files: import every `~/.amber/staging/*.jsonl` line into the
+/// event store, then remove the file so this only ever runs once. A crash
+/// mid-write could have left a truncated trailing line, so this goes
+/// through the same resilient parse a streaming consumer would use rather
+/// than a naive line-by-line JSON parse.
+async fn import_legacy_jsonl(db: &Db, base: &Path) -> Result<(), AmberError> {
+    let staging_dir = base.join("staging");
+    let mut entries = match tokio::fs::read_dir(&staging_dir).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(AmberError::Io(e)),
+    };
+
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("jsonl") {
+            continue;
+        }
+        let Some(date) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+
+        let content = tokio::fs::read(&path).await?;
+        let (events, trailing, mut skipped) = parse_jsonl_resilient(&content);
+        if !trailing.is_empty() {
+            warn!(
+                "Dropping truncated trailing line in {}",
+                path.display()
+            );
+            skipped += 1;
+        }
+
+        for event in &events {
+            let line = serde_json::to_string(event)?;
+            let key = event_key(date, event.timestamp(), &line);
+            db.insert(key, line.as_bytes()).map_err(sled_err)?;
+        }
+
+        tokio::fs::remove_file(&path).await?;
+        info!(
+            "Imported {} legacy staging events from {} ({} lines skipped)",
+            events.len(),
+            path.display(),
+            skipped
+        );
+    }
+
+    Ok(())
+}
+
+async fn events_db() -> Result<&'static Db, AmberError> {
+    EVENTS_DB
+        .get_or_try_init(|| async {
+            let base = amber_dir()?;
+            tokio::fs::create_dir_all(&base).await?;
+
+            let db = sled::open(base.join("events.sled")).map_err(sled_err)?;
+            import_legacy_jsonl(&db, &base).await?;
+
+            Ok::<_, AmberError>(db)
+        })
+        .await
+}
+
+async fn pool() -> Result<&'static SqlitePool, AmberError> {
+    POOL.get_or_try_init(|| async {
+        let base = amber_dir()?;
+        tokio::fs::create_dir_all(&base).await?;
+
+        let options = SqliteConnectOptions::new()
+            .filename(base.join("amber.db"))
+            .create_if_missing(true);
+
+        // A small pool so daily-note writes and note-chunk search reads
+        // don't serialize on a single connection.
+        let pool = SqlitePoolOptions::new()
+            .max_connections(5)
+            .connect_with(options)
+            .await?;
+
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS daily_notes (
+                date TEXT PRIMARY KEY,
+                content TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            )",
+        )
+        .execute(&pool)
+        .await?;
+
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS note_chunks (
+                date TEXT NOT NULL,
+                chunk_index INTEGER NOT NULL,
+                chunk_text TEXT NOT NULL,
+                vector BLOB NOT NULL,
+                PRIMARY KEY (date, chunk_index)
+            )",
+        )
+        .execute(&pool)
+        .await?;
+
+        Ok::<_, AmberError>(pool)
+    })
+    .await
+}
+
 pub async fn ensure_dirs() -> Result<(), AmberError> {
     let base = amber_dir()?;
     tokio::fs::create_dir_all(base.join("daily")).await?;
-    tokio::fs::create_dir_all(base.join("staging")).await?;
+    // Opens (and migrates) both stores so later calls don't pay for it.
+    pool().await?;
+    events_db().await?;
     Ok(())
 }
 
@@ -28,38 +248,329 @@ pub async fn read_daily_note(date: &str) -> Result<Option<String>, AmberError> {
 pub async fn write_daily_note(date: &str, content: &str) -> Result<(), AmberError> {
     let path = amber_dir()?.join("daily").join(format!("{}.md", date));
     tokio::fs::write(&path, content).await?;
+
+    let db = pool().await?;
+    sqlx::query(
+        "INSERT INTO daily_notes (date, content, created_at)
+         VALUES (?1, ?2, datetime('now'))
+         ON CONFLICT(date) DO UPDATE SET content = excluded.content, created_at = excluded.created_at",
+    )
+    .bind(date)
+    .bind(content)
+    .execute(db)
+    .await?;
+
     Ok(())
 }
 
+/// Append a serialized `RawEvent` to the store for `date`. The key encodes
+/// a content hash, so a re-emitted event (the git watcher can re-fire for a
+/// commit it already reported) overwrites its own entry instead of
+/// duplicating it - no read-before-write needed.
 pub async fn append_staging_event(date: &str, event: &str) -> Result<(), AmberError> {
-    let path = amber_dir()?.join("staging").join(format!("{}.jsonl", date));
-    let mut file = tokio::fs::OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(&path)
-        .await?;
-    file.write_all(format!("{}\n", event).as_bytes()).await?;
+    let parsed: RawEvent = serde_json::from_str(event)?;
+    let db = events_db().await?;
+    let key = event_key(date, parsed.timestamp(), event);
+    db.insert(key, event.as_bytes()).map_err(sled_err)?;
     Ok(())
 }
 
+/// Read every not-yet-summarized event for `date`, in timestamp order. Thin
+/// wrapper over `query_events` kept for `summarize_day` and any other
+/// caller that just wants "today's events" - this is what turns
+/// `summarize_day` into a range query instead of a whole-file read.
 pub async fn read_staging_events(date: &str) -> Result<Vec<String>, AmberError> {
-    let path = amber_dir()?.join("staging").join(format!("{}.jsonl", date));
-    match tokio::fs::read_to_string(&path).await {
-        Ok(content) => Ok(content
-            .lines()
-            .filter(|l| !l.is_empty())
-            .map(String::from)
-            .collect()),
-        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(vec![]),
-        Err(e) => Err(AmberError::Io(e)),
+    let mut events = Vec::new();
+    for record in query_events(None, date, date).await? {
+        if record.summarized_at.is_some() {
+            continue;
+        }
+        match serde_json::to_string(&record_to_raw_event(&record)) {
+            Ok(json) => events.push(json),
+            Err(e) => warn!("Skipping unserializable staging event: {}", e),
+        }
+    }
+    Ok(events)
+}
+
+/// Rebuild the `RawEvent` an `EventRecord` was flattened from, so
+/// `read_staging_events` can hand `summarize_day` the same serialized form
+/// a direct staging read would have. Never fails the whole batch over one
+/// bad row: a `"commit"`-kind record whose `data` doesn't actually parse as
+/// `CommitFields` (reachable via a webhook POST that lies about its kind)
+/// falls back to a `Dynamic` event instead, the same way an unrecognized
+/// watcher payload already does.
+fn record_to_raw_event(record: &EventRecord) -> RawEvent {
+    if record.kind == "commit" {
+        match serde_json::from_value::<CommitFields>(record.data.clone()) {
+            Ok(fields) => {
+                return RawEvent::commit(
+                    record.source.clone(),
+                    record.timestamp.clone(),
+                    fields.repo,
+                    fields.hash,
+                    fields.subject,
+                    fields.author,
+                );
+            }
+            Err(e) => warn!(
+                "Event claims kind \"commit\" but data doesn't match CommitFields ({}); keeping it as dynamic",
+                e
+            ),
+        }
     }
+
+    RawEvent::dynamic(
+        record.source.clone(),
+        record.timestamp.clone(),
+        record.kind.clone(),
+        record.data.clone(),
+    )
 }
 
+/// Count `date`'s un-summarized events, optionally restricted to one
+/// source, for per-source "buffered events" status reporting.
+pub async fn count_unsummarized(source: Option<&str>, date: &str) -> Result<usize, AmberError> {
+    let db = events_db().await?;
+    let summarized = db.open_tree(SUMMARIZED_TREE).map_err(sled_err)?;
+
+    let mut count = 0;
+    for item in db.scan_prefix(date_prefix(date)) {
+        let (key, value) = item.map_err(sled_err)?;
+        if summarized.contains_key(&key).map_err(sled_err)? {
+            continue;
+        }
+        if let Some(source) = source {
+            let parsed: RawEvent = serde_json::from_slice(&value)?;
+            if parsed.source() != source {
+                continue;
+            }
+        }
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Mark `date`'s un-summarized events as summarized rather than deleting
+/// them, so a note can be regenerated later without re-ingesting events.
 pub async fn clear_staging(date: &str) -> Result<(), AmberError> {
-    let path = amber_dir()?.join("staging").join(format!("{}.jsonl", date));
-    match tokio::fs::remove_file(&path).await {
-        Ok(()) => Ok(()),
-        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
-        Err(e) => Err(AmberError::Io(e)),
+    let db = events_db().await?;
+    let summarized = db.open_tree(SUMMARIZED_TREE).map_err(sled_err)?;
+
+    let marked_at = chrono::Local::now().to_rfc3339();
+    for item in db.scan_prefix(date_prefix(date)) {
+        let (key, _) = item.map_err(sled_err)?;
+        if !summarized.contains_key(&key).map_err(sled_err)? {
+            summarized.insert(key, marked_at.as_bytes()).map_err(sled_err)?;
+        }
+    }
+    Ok(())
+}
+
+/// Undo `clear_staging`'s marks for `date`, so its events are eligible for
+/// `read_staging_events` again. Used by `replay_events` so a day that
+/// already has a note can still be regenerated from a recording - without
+/// this, every replayed event would come back byte-identical (and so key-
+/// identical) but stay marked summarized, and `summarize_day` would read
+/// zero events.
+pub async fn unmark_summarized(date: &str) -> Result<(), AmberError> {
+    let db = events_db().await?;
+    let summarized = db.open_tree(SUMMARIZED_TREE).map_err(sled_err)?;
+
+    for item in db.scan_prefix(date_prefix(date)) {
+        let (key, _) = item.map_err(sled_err)?;
+        summarized.remove(&key).map_err(sled_err)?;
+    }
+    Ok(())
+}
+
+/// Returns true if `fingerprint` was already recorded as sent within
+/// `window`, so the caller should suppress the notification it identifies.
+/// Does not itself record anything - a duplicate check that's about to be
+/// followed by a possibly-failing send must not mark the fingerprint sent
+/// until the send actually succeeds; see `record_notification_sent`.
+/// Expired entries are swept opportunistically on every call so the tree
+/// doesn't grow unbounded.
+pub async fn is_duplicate_notification(
+    fingerprint: u64,
+    window: Duration,
+) -> Result<bool, AmberError> {
+    let db = events_db().await?;
+    let tree = db.open_tree(NOTIFICATIONS_TREE).map_err(sled_err)?;
+    let now = chrono::Local::now();
+
+    let key = fingerprint.to_be_bytes();
+    let mut duplicate = false;
+    if let Some(value) = tree.get(key).map_err(sled_err)? {
+        if let Some(sent_at) = parse_rfc3339(&value) {
+            if (now - sent_at).to_std().unwrap_or(Duration::MAX) < window {
+                duplicate = true;
+            }
+        }
+    }
+
+    for item in tree.iter() {
+        let (stale_key, value) = item.map_err(sled_err)?;
+        let expired = match parse_rfc3339(&value) {
+            Some(sent_at) => (now - sent_at).to_std().unwrap_or(Duration::MAX) >= window,
+            None => true,
+        };
+        if expired {
+            tree.remove(stale_key).map_err(sled_err)?;
+        }
+    }
+
+    Ok(duplicate)
+}
+
+/// Persists `fingerprint` as sent-now, so a subsequent `is_duplicate_notification`
+/// call within the dedup window reports it as a duplicate. Callers should only
+/// call this once the send it's guarding has actually succeeded - recording it
+/// any earlier would suppress a legitimate retry after a failed send.
+pub async fn record_notification_sent(fingerprint: u64) -> Result<(), AmberError> {
+    let db = events_db().await?;
+    let tree = db.open_tree(NOTIFICATIONS_TREE).map_err(sled_err)?;
+    tree.insert(
+        fingerprint.to_be_bytes(),
+        chrono::Local::now().to_rfc3339().as_bytes(),
+    )
+    .map_err(sled_err)?;
+    Ok(())
+}
+
+fn parse_rfc3339(bytes: &[u8]) -> Option<chrono::DateTime<chrono::Local>> {
+    String::from_utf8_lossy(bytes)
+        .parse::<chrono::DateTime<chrono::FixedOffset>>()
+        .ok()
+        .map(|dt| dt.with_timezone(&chrono::Local))
+}
+
+/// Date-range (and optional source) query over the event store, for
+/// cross-day views a single day's worth of staging couldn't support. Scans
+/// every key since sled has no secondary index on date range - fine at the
+/// volumes a single user's daily notes produce.
+pub async fn query_events(
+    source: Option<&str>,
+    from: &str,
+    to: &str,
+) -> Result<Vec<EventRecord>, AmberError> {
+    let db = events_db().await?;
+    let summarized = db.open_tree(SUMMARIZED_TREE).map_err(sled_err)?;
+
+    let mut records = Vec::new();
+    for item in db.iter() {
+        let (key, value) = item.map_err(sled_err)?;
+        let date = key_date(&key);
+        if date.as_str() < from || date.as_str() > to {
+            continue;
+        }
+
+        let parsed: RawEvent = serde_json::from_slice(&value)?;
+        if let Some(source) = source {
+            if parsed.source() != source {
+                continue;
+            }
+        }
+
+        let summarized_at = summarized
+            .get(&key)
+            .map_err(sled_err)?
+            .map(|v| String::from_utf8_lossy(&v).to_string());
+
+        records.push(EventRecord {
+            source: parsed.source().to_string(),
+            timestamp: parsed.timestamp().to_string(),
+            kind: parsed.kind_label().to_string(),
+            data: parsed.data_json(),
+            date,
+            summarized_at,
+        });
+    }
+
+    records.sort_by(|a, b| (a.date.as_str(), a.timestamp.as_str()).cmp(&(b.date.as_str(), b.timestamp.as_str())));
+    Ok(records)
+}
+
+/// A normalized note chunk and its embedding, as returned by
+/// `all_note_chunks` for the search module's brute-force cosine scan.
+pub struct NoteChunk {
+    pub date: String,
+    pub chunk_text: String,
+    pub vector: Vec<f32>,
+}
+
+/// Length-prefixed little-endian f32 encoding: a u32 dimension count
+/// followed by that many f32 values. Self-describing, so decoding never
+/// depends on the raw blob length matching a fixed vector size.
+fn encode_vector(vector: &[f32]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(4 + vector.len() * 4);
+    bytes.extend_from_slice(&(vector.len() as u32).to_le_bytes());
+    for value in vector {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    bytes
+}
+
+fn decode_vector(bytes: &[u8]) -> Vec<f32> {
+    if bytes.len() < 4 {
+        return Vec::new();
+    }
+    let len = u32::from_le_bytes(bytes[0..4].try_into().unwrap()) as usize;
+    bytes[4..]
+        .chunks_exact(4)
+        .take(len)
+        .map(|c| f32::from_le_bytes(c.try_into().unwrap()))
+        .collect()
+}
+
+/// Replace all chunks for `date` with `chunks`/`vectors` (already
+/// normalized by the caller, so search is a plain dot product).
+pub async fn replace_note_chunks(
+    date: &str,
+    chunks: &[String],
+    vectors: &[Vec<f32>],
+) -> Result<(), AmberError> {
+    let db = pool().await?;
+    let mut tx = db.begin().await?;
+
+    sqlx::query("DELETE FROM note_chunks WHERE date = ?1")
+        .bind(date)
+        .execute(&mut *tx)
+        .await?;
+
+    for (index, (text, vector)) in chunks.iter().zip(vectors.iter()).enumerate() {
+        sqlx::query(
+            "INSERT INTO note_chunks (date, chunk_index, chunk_text, vector)
+             VALUES (?1, ?2, ?3, ?4)",
+        )
+        .bind(date)
+        .bind(index as i64)
+        .bind(text)
+        .bind(encode_vector(vector))
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// All indexed note chunks, for the search module's exact brute-force scan
+/// (note volumes are small enough that this is cheap).
+pub async fn all_note_chunks() -> Result<Vec<NoteChunk>, AmberError> {
+    let db = pool().await?;
+    let rows = sqlx::query("SELECT date, chunk_text, vector FROM note_chunks")
+        .fetch_all(db)
+        .await?;
+
+    let mut chunks = Vec::with_capacity(rows.len());
+    for row in rows {
+        let vector_bytes: Vec<u8> = row.try_get("vector")?;
+        chunks.push(NoteChunk {
+            date: row.try_get("date")?,
+            chunk_text: row.try_get("chunk_text")?,
+            vector: decode_vector(&vector_bytes),
+        });
     }
+    Ok(chunks)
 }
@@ -0,0 +1,71 @@
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::routing::get;
+use axum::{Extension, Router};
+use futures_util::Stream;
+use log::{error, info};
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::StreamExt;
+
+use crate::watchers::RawEvent;
+
+/// Fan-out channel for `RawEvent`s, installed alongside the watcher
+/// registry's mpsc `tx` so the frontend can see activity as it arrives
+/// instead of only once a day via the summarizer.
+pub type EventBus = Arc<broadcast::Sender<RawEvent>>;
+
+pub fn new_bus() -> EventBus {
+    let (tx, _rx) = broadcast::channel(256);
+    Arc::new(tx)
+}
+
+pub fn subscribe(bus: &EventBus) -> broadcast::Receiver<RawEvent> {
+    bus.subscribe()
+}
+
+async fn sse_handler(
+    Extension(bus): Extension<EventBus>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let events = BroadcastStream::new(subscribe(&bus)).filter_map(|msg| match msg {
+        Ok(event) => serde_json::to_string(&event)
+            .ok()
+            .map(|json| Ok(Event::default().data(json))),
+        // A slow subscriber lagged behind and missed some events; keep
+        // streaming rather than ending the connection.
+        Err(_) => None,
+    });
+
+    Sse::new(events).keep_alive(
+        KeepAlive::new()
+            .interval(Duration::from_secs(15))
+            .text("keep-alive"),
+    )
+}
+
+/// Serve `GET /events` as a Server-Sent-Events stream of every `RawEvent`
+/// broadcast on `bus`, so a long-lived frontend connection can show a live
+/// activity feed.
+pub fn spawn_server(bus: EventBus, bind_addr: String) {
+    tauri::async_runtime::spawn(async move {
+        let app = Router::new()
+            .route("/events", get(sse_handler))
+            .layer(Extension(bus));
+
+        let listener = match tokio::net::TcpListener::bind(&bind_addr).await {
+            Ok(listener) => listener,
+            Err(e) => {
+                error!("Failed to bind activity stream on {}: {}", bind_addr, e);
+                return;
+            }
+        };
+
+        info!("Activity stream listening on {}", bind_addr);
+        if let Err(e) = axum::serve(listener, app).await {
+            error!("Activity stream server error: {}", e);
+        }
+    });
+}
@@ -1,6 +1,7 @@
 use crate::config::SummarizerConfig;
 use crate::error::AmberError;
 use crate::summarizer::provider::{LlmProvider, Message, OpenAICompatibleProvider};
+use crate::watchers::RawEvent;
 
 pub struct DailySummarizer;
 
@@ -16,7 +17,18 @@ impl DailySummarizer {
             api_key_env: config.api_key_env.clone(),
         };
 
-        let events_text = events.join("\n");
+        // Render each event as a readable line when it parses, Checked or
+        // Dynamic alike, so the prompt isn't raw JSON the model has to
+        // unpack itself.
+        let events_text = events
+            .iter()
+            .map(|event| {
+                serde_json::from_str::<RawEvent>(event)
+                    .map(|parsed| parsed.render_line())
+                    .unwrap_or_else(|_| event.clone())
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
 
         let system_prompt = format!(
             "You are a personal knowledge assistant. Generate a daily development note for {}.\n\
@@ -3,11 +3,13 @@ pub mod provider;
 
 use chrono::{Local, Timelike};
 use log::{error, info};
-use tauri::Listener;
+use tauri::{Listener, Manager};
 
 use crate::config::AmberConfig;
 use crate::error::AmberError;
+use crate::notifier;
 use crate::storage;
+use crate::watchers::{self, CheckedEvent, CheckedKind, RawEvent, SharedWatchers};
 
 pub fn run_scheduler(app_handle: tauri::AppHandle) {
     // Bridge the Tauri event to a tokio channel for the async scheduler loop
@@ -17,6 +19,10 @@ pub fn run_scheduler(app_handle: tauri::AppHandle) {
         let _ = manual_tx.send(());
     });
 
+    let state = app_handle.state::<std::sync::Mutex<crate::AppState>>();
+    let watchers = state.lock().unwrap().watchers.clone();
+    let handle = app_handle.clone();
+
     tauri::async_runtime::spawn(async move {
         let config = match crate::config::load_or_default() {
             Ok(c) => c,
@@ -46,7 +52,7 @@ pub fn run_scheduler(app_handle: tauri::AppHandle) {
                     if now.hour() == config.schedule.daily_hour && last_daily_date != today {
                         info!("Daily summarization triggered for {}", today);
                         last_daily_date.clone_from(&today);
-                        if let Err(e) = summarize_day(&today, &config).await {
+                        if let Err(e) = summarize_day(&today, &config, &watchers, &handle).await {
                             error!("Summarization failed: {}", e);
                         }
                     }
@@ -54,7 +60,7 @@ pub fn run_scheduler(app_handle: tauri::AppHandle) {
                 _ = manual_rx.recv() => {
                     let today = Local::now().format("%Y-%m-%d").to_string();
                     info!("Manual summarization triggered for {}", today);
-                    if let Err(e) = summarize_day(&today, &config).await {
+                    if let Err(e) = summarize_day(&today, &config, &watchers, &handle).await {
                         error!("Manual summarization failed: {}", e);
                     }
                 }
@@ -63,17 +69,64 @@ pub fn run_scheduler(app_handle: tauri::AppHandle) {
     });
 }
 
-pub async fn summarize_day(date: &str, config: &AmberConfig) -> Result<(), AmberError> {
+pub async fn summarize_day(
+    date: &str,
+    config: &AmberConfig,
+    watchers: &SharedWatchers,
+    app_handle: &tauri::AppHandle,
+) -> Result<(), AmberError> {
+    // Make sure every event already queued in the watcher pipeline has been
+    // drained into staging before we read it, so a just-made commit can't
+    // race a manual "Summarize Now".
+    watchers::flush_all(watchers).await?;
+
     let events = storage::read_staging_events(date).await?;
     if events.is_empty() {
         info!("No events to summarize for {}", date);
         return Ok(());
     }
 
+    let (commit_count, repo_count) = count_commits_and_repos(&events);
+
     let note = daily::DailySummarizer::generate(date, events, &config.summarizer).await?;
     storage::write_daily_note(date, &note).await?;
     storage::clear_staging(date).await?;
 
+    if let Err(e) = crate::search::index_note(date, &note, &config.summarizer).await {
+        error!("Failed to index daily note for search: {}", e);
+    }
+
     info!("Daily note written for {}", date);
+    notifier::notify_summary_ready(
+        app_handle,
+        &config.notifications,
+        date,
+        commit_count,
+        repo_count,
+    )
+    .await;
+
+    if let Some(state) = app_handle.try_state::<std::sync::Mutex<crate::AppState>>() {
+        if let Ok(mut state) = state.lock() {
+            state.last_summarized = Some(date.to_string());
+        }
+    }
+
     Ok(())
 }
+
+fn count_commits_and_repos(events: &[String]) -> (usize, usize) {
+    let mut repos = std::collections::HashSet::new();
+    let mut commit_count = 0;
+    for event in events {
+        if let Ok(RawEvent::Checked(CheckedEvent {
+            kind: CheckedKind::Commit(fields),
+            ..
+        })) = serde_json::from_str::<RawEvent>(event)
+        {
+            commit_count += 1;
+            repos.insert(fields.repo);
+        }
+    }
+    (commit_count, repos.len())
+}
@@ -71,3 +71,57 @@ impl LlmProvider for OpenAICompatibleProvider {
             .ok_or_else(|| AmberError::Provider("No content in response".into()))
     }
 }
+
+#[async_trait]
+pub trait Embedder: Send + Sync {
+    async fn embed(&self, inputs: Vec<String>) -> Result<Vec<Vec<f32>>, AmberError>;
+}
+
+pub struct OpenAICompatibleEmbedder {
+    pub api_base: String,
+    pub model: String,
+    pub api_key_env: String,
+}
+
+#[async_trait]
+impl Embedder for OpenAICompatibleEmbedder {
+    async fn embed(&self, inputs: Vec<String>) -> Result<Vec<Vec<f32>>, AmberError> {
+        let api_key = std::env::var(&self.api_key_env).map_err(|_| {
+            AmberError::Provider(format!("Missing env var: {}", self.api_key_env))
+        })?;
+
+        let client = reqwest::Client::new();
+        let url = format!("{}/embeddings", self.api_base);
+
+        let body = serde_json::json!({
+            "model": self.model,
+            "input": inputs,
+        });
+
+        let resp = client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", api_key))
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| AmberError::Provider(format!("Request failed: {}", e)))?;
+
+        let json: serde_json::Value = resp
+            .json()
+            .await
+            .map_err(|e| AmberError::Provider(format!("Failed to parse response: {}", e)))?;
+
+        let data = json["data"]
+            .as_array()
+            .ok_or_else(|| AmberError::Provider("No data in embeddings response".into()))?;
+
+        data.iter()
+            .map(|item| {
+                item["embedding"]
+                    .as_array()
+                    .map(|vec| vec.iter().filter_map(|v| v.as_f64()).map(|v| v as f32).collect())
+                    .ok_or_else(|| AmberError::Provider("No embedding in response item".into()))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,104 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use log::warn;
+
+use crate::config::CommandSourceConfig;
+use crate::error::AmberError;
+use crate::watchers::{RawEvent, Watcher};
+
+/// A generic activity source: periodically runs a configured shell command
+/// and emits any stdout lines not seen on the previous run as `RawEvent`s.
+/// Lets users wire in shell history, `pmset -g batterylog`, a calendar CLI,
+/// or anything else that prints new lines over time, without a dedicated
+/// `Watcher` impl per tool.
+pub struct CommandWatcher {
+    config: CommandSourceConfig,
+    running: Arc<AtomicBool>,
+}
+
+impl CommandWatcher {
+    pub fn new(config: CommandSourceConfig) -> Self {
+        Self {
+            config,
+            running: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+async fn run_command(command: &str, args: &[String]) -> Result<String, AmberError> {
+    let output = tokio::process::Command::new(command)
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| AmberError::Watcher(format!("Failed to run '{}': {}", command, e)))?;
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[async_trait]
+impl Watcher for CommandWatcher {
+    async fn start(
+        &mut self,
+        tx: tokio::sync::mpsc::Sender<RawEvent>,
+    ) -> Result<(), AmberError> {
+        self.running.store(true, Ordering::SeqCst);
+
+        let running = self.running.clone();
+        let config = self.config.clone();
+        let interval = Duration::from_secs(config.interval_secs.max(1));
+
+        tauri::async_runtime::spawn(async move {
+            let mut last_output = String::new();
+            let mut ticker = tokio::time::interval(interval);
+
+            while running.load(Ordering::SeqCst) {
+                ticker.tick().await;
+
+                match run_command(&config.command, &config.args).await {
+                    Ok(output) => {
+                        let new_lines: Vec<&str> = output
+                            .lines()
+                            .filter(|line| !last_output.lines().any(|seen| seen == *line))
+                            .collect();
+
+                        for line in &new_lines {
+                            // A command's stdout line isn't a known
+                            // `CheckedKind`, so it travels as a `Dynamic`
+                            // event rather than being shoehorned into Commit.
+                            let raw_event = RawEvent::dynamic(
+                                config.name.clone(),
+                                chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+                                "command_output",
+                                serde_json::json!({ "line": line }),
+                            );
+                            if tx.send(raw_event).await.is_err() {
+                                return;
+                            }
+                        }
+
+                        last_output = output;
+                    }
+                    Err(e) => {
+                        warn!("Command watcher '{}' failed: {}", config.name, e);
+                    }
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    fn stop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+
+    fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+}
@@ -1,23 +1,47 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use async_trait::async_trait;
 use log::{error, info, warn};
 use notify::RecursiveMode;
 use notify_debouncer_mini::{new_debouncer, DebounceEventResult};
+use tokio::sync::oneshot;
 
 use crate::config::GitSourceConfig;
 use crate::error::AmberError;
-use crate::watchers::{EventKind, RawEvent, Watcher};
+use crate::watchers::{RawEvent, Watcher};
+
+/// Prefix used for the sentinel files `flush()` writes into a watched
+/// `refs/heads` directory. This is only the first half of the barrier: it
+/// lets the debouncer's per-watcher event ordering tell us that every real
+/// filesystem event queued before the `flush()` call that minted cookie *N*
+/// has already been delivered to our debounce loop. Getting those events
+/// all the way into staging is the second half - see
+/// `RawEvent::flush_cookie` and `resolve_flush_cookie` below.
+const COOKIE_PREFIX: &str = ".amber-cookie-";
+
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Waiters registered by in-flight `flush()` calls, keyed by the serial of
+/// the cookie they're waiting on. Resolved once `run_all`'s receive loop
+/// has dequeued (and therefore already staged everything ahead of) the
+/// matching `RawEvent::flush_cookie` sentinel - not when the debounce loop
+/// merely observes the cookie file.
+type CookieWaiters = Arc<Mutex<BTreeMap<u64, oneshot::Sender<()>>>>;
 
 pub struct GitWatcher {
     config: GitSourceConfig,
     running: Arc<AtomicBool>,
     // Hold the debouncer so file watches stay active
     _debouncer: Option<notify_debouncer_mini::Debouncer<notify::RecommendedWatcher>>,
+    // A watched refs/heads directory to drop flush cookies into. `None` if
+    // no repos are being watched, in which case flush() is a no-op.
+    cookie_dir: Option<PathBuf>,
+    cookie_serial: Arc<AtomicU64>,
+    cookie_waiters: CookieWaiters,
 }
 
 impl GitWatcher {
@@ -26,6 +50,28 @@ impl GitWatcher {
             config,
             running: Arc::new(AtomicBool::new(false)),
             _debouncer: None,
+            cookie_dir: None,
+            cookie_serial: Arc::new(AtomicU64::new(0)),
+            cookie_waiters: Arc::new(Mutex::new(BTreeMap::new())),
+        }
+    }
+}
+
+fn cookie_serial_from_path(path: &Path) -> Option<u64> {
+    let name = path.file_name()?.to_str()?;
+    name.strip_prefix(COOKIE_PREFIX)?.parse().ok()
+}
+
+/// Resolve (and remove) every waiter registered for a serial `<= serial`.
+/// Called from `run_all`'s receive loop once it has dequeued the matching
+/// flush-cookie sentinel - at which point every real event this watcher
+/// queued ahead of it is already staged.
+fn resolve_waiters_up_to(waiters: &CookieWaiters, serial: u64) {
+    let mut guard = waiters.lock().unwrap();
+    let pending: Vec<u64> = guard.range(..=serial).map(|(k, _)| *k).collect();
+    for key in pending {
+        if let Some(tx) = guard.remove(&key) {
+            let _ = tx.send(());
         }
     }
 }
@@ -121,8 +167,6 @@ impl Watcher for GitWatcher {
         &mut self,
         tx: tokio::sync::mpsc::Sender<RawEvent>,
     ) -> Result<(), AmberError> {
-        self.running.store(true, Ordering::SeqCst);
-
         let repos = discover_repos(&self.config)?;
         if repos.is_empty() {
             warn!("No git repos found under watch paths");
@@ -131,6 +175,8 @@ impl Watcher for GitWatcher {
 
         info!("Discovered {} git repos", repos.len());
 
+        self.running.store(true, Ordering::SeqCst);
+
         // Bridge notify's sync callbacks to tokio via an unbounded channel
         let (debounce_tx, mut debounce_rx) =
             tokio::sync::mpsc::unbounded_channel::<DebounceEventResult>();
@@ -147,6 +193,10 @@ impl Watcher for GitWatcher {
             if refs_path.exists() {
                 if let Err(e) = debouncer.watcher().watch(&refs_path, RecursiveMode::Recursive) {
                     warn!("Failed to watch {}: {}", refs_path.display(), e);
+                } else if self.cookie_dir.is_none() {
+                    // Any one watched directory works as a flush barrier, since
+                    // notify preserves per-watcher event ordering.
+                    self.cookie_dir = Some(refs_path);
                 }
             }
         }
@@ -162,9 +212,16 @@ impl Watcher for GitWatcher {
             while running.load(Ordering::SeqCst) {
                 match debounce_rx.recv().await {
                     Some(Ok(events)) => {
-                        // Deduplicate repos from this batch of events
+                        // Deduplicate repos from this batch of events, and
+                        // collect any flush() cookies observed along the way.
                         let mut changed_repos = Vec::new();
+                        let mut cookie_serials = Vec::new();
                         for event in &events {
+                            if let Some(serial) = cookie_serial_from_path(&event.path) {
+                                cookie_serials.push(serial);
+                                let _ = std::fs::remove_file(&event.path);
+                                continue;
+                            }
                             if let Some(repo) =
                                 repo_from_event_path(&event.path, &repos_clone)
                             {
@@ -185,17 +242,14 @@ impl Watcher for GitWatcher {
                                                 break;
                                             }
                                         }
-                                        let raw_event = RawEvent {
-                                            source: "git".to_string(),
-                                            timestamp: date.clone(),
-                                            kind: EventKind::Commit,
-                                            data: serde_json::json!({
-                                                "repo": repo.display().to_string(),
-                                                "hash": hash,
-                                                "subject": subject,
-                                                "author": author,
-                                            }),
-                                        };
+                                        let raw_event = RawEvent::commit(
+                                            "git",
+                                            date.clone(),
+                                            repo.display().to_string(),
+                                            hash,
+                                            subject,
+                                            author,
+                                        );
                                         if tx.send(raw_event).await.is_err() {
                                             return;
                                         }
@@ -213,6 +267,18 @@ impl Watcher for GitWatcher {
                                 }
                             }
                         }
+
+                        // Sent after every real commit event from this batch,
+                        // so the mpsc channel's FIFO ordering - plus
+                        // `run_all` processing it sequentially - is what
+                        // guarantees the corresponding `flush()` call can't
+                        // observe this resolved before those commits are
+                        // staged.
+                        for serial in cookie_serials {
+                            if tx.send(RawEvent::flush_cookie("git", serial)).await.is_err() {
+                                return;
+                            }
+                        }
                     }
                     Some(Err(err)) => {
                         error!("Debouncer error: {:?}", err);
@@ -229,4 +295,49 @@ impl Watcher for GitWatcher {
         self.running.store(false, Ordering::SeqCst);
         self._debouncer = None;
     }
+
+    fn name(&self) -> &str {
+        "git"
+    }
+
+    fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    fn resolve_flush_cookie(&self, serial: u64) {
+        resolve_waiters_up_to(&self.cookie_waiters, serial);
+    }
+
+    async fn flush(&self) -> Result<(), AmberError> {
+        let Some(cookie_dir) = self.cookie_dir.clone() else {
+            // No repos watched, so there's nothing that could be in flight.
+            return Ok(());
+        };
+
+        let serial = self.cookie_serial.fetch_add(1, Ordering::SeqCst) + 1;
+        let (tx, rx) = oneshot::channel();
+        self.cookie_waiters.lock().unwrap().insert(serial, tx);
+
+        let cookie_path = cookie_dir.join(format!("{}{}", COOKIE_PREFIX, serial));
+        if let Err(e) = tokio::fs::write(&cookie_path, b"").await {
+            self.cookie_waiters.lock().unwrap().remove(&serial);
+            return Err(AmberError::Watcher(format!(
+                "Failed to write flush cookie: {}",
+                e
+            )));
+        }
+
+        match tokio::time::timeout(FLUSH_TIMEOUT, rx).await {
+            Ok(Ok(())) => Ok(()),
+            _ => {
+                self.cookie_waiters.lock().unwrap().remove(&serial);
+                // The debounce loop only unlinks a cookie file once it
+                // observes it - on a timeout that may never happen, so
+                // clean it up here instead of leaving it behind in the
+                // user's `refs/heads` directory.
+                let _ = tokio::fs::remove_file(&cookie_path).await;
+                Err(AmberError::Watcher("cookie timeout".into()))
+            }
+        }
+    }
 }
@@ -1,34 +1,298 @@
+pub mod command;
 pub mod git;
+pub mod registry;
+pub mod webhook;
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
 
 use async_trait::async_trait;
-use log::{error, info};
+use log::error;
 use serde::{Deserialize, Serialize};
+use tauri::{Emitter, Manager};
+use tokio::sync::oneshot;
 
 use crate::error::AmberError;
 
+/// A fully-parsed, strongly-typed event payload. Closed on purpose: adding a
+/// source whose events deserve first-class fields (issues, PRs, calendar)
+/// means adding a variant here, not loosening this back into a bag of JSON.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "data", rename_all = "snake_case")]
+pub enum CheckedKind {
+    Commit(CommitFields),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitFields {
+    pub repo: String,
+    pub hash: String,
+    pub subject: String,
+    pub author: String,
+}
+
+/// An event whose `kind` matched a known `CheckedKind` variant.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckedEvent {
+    pub source: String,
+    pub timestamp: String,
+    #[serde(flatten)]
+    pub kind: CheckedKind,
+}
+
+/// Fallback for a `kind` that isn't (yet) one of `CheckedKind`'s variants.
+/// The raw JSON is preserved untouched so an unrecognized or future watcher
+/// payload is never dropped, just left un-typed.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct RawEvent {
+pub struct DynamicEvent {
     pub source: String,
     pub timestamp: String,
-    pub kind: EventKind,
+    pub kind: String,
     pub data: serde_json::Value,
 }
 
+/// An event flowing through the pipeline. Deserialization tries the typed
+/// `Checked` path first and only falls back to `Dynamic` if the payload
+/// doesn't match a known `CheckedKind` - `#[serde(untagged)]` gives us that
+/// try-then-fallback order for free.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum EventKind {
-    Commit,
+#[serde(untagged)]
+pub enum RawEvent {
+    Checked(CheckedEvent),
+    Dynamic(DynamicEvent),
+}
+
+/// Reserved `kind` for [`RawEvent::flush_cookie`] sentinels.
+const FLUSH_COOKIE_KIND: &str = "__flush_cookie__";
+
+/// Reserved `source` for a [`RawEvent::flush_cookie`] sentinel pushed by
+/// [`drain_barrier`] rather than by a registered `Watcher` - there's no
+/// `Watcher` instance to dispatch to, so `run_all` resolves these straight
+/// out of `drain_waiters` instead of calling `resolve_flush_cookie`.
+const DRAIN_BARRIER_SOURCE: &str = "__drain_barrier__";
+
+type DrainWaiters = Mutex<HashMap<u64, oneshot::Sender<()>>>;
+
+fn drain_waiters() -> &'static DrainWaiters {
+    static WAITERS: OnceLock<DrainWaiters> = OnceLock::new();
+    WAITERS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+impl RawEvent {
+    pub fn commit(
+        source: impl Into<String>,
+        timestamp: impl Into<String>,
+        repo: impl Into<String>,
+        hash: impl Into<String>,
+        subject: impl Into<String>,
+        author: impl Into<String>,
+    ) -> Self {
+        RawEvent::Checked(CheckedEvent {
+            source: source.into(),
+            timestamp: timestamp.into(),
+            kind: CheckedKind::Commit(CommitFields {
+                repo: repo.into(),
+                hash: hash.into(),
+                subject: subject.into(),
+                author: author.into(),
+            }),
+        })
+    }
+
+    pub fn dynamic(
+        source: impl Into<String>,
+        timestamp: impl Into<String>,
+        kind: impl Into<String>,
+        data: serde_json::Value,
+    ) -> Self {
+        RawEvent::Dynamic(DynamicEvent {
+            source: source.into(),
+            timestamp: timestamp.into(),
+            kind: kind.into(),
+            data,
+        })
+    }
+
+    /// A flush-barrier sentinel a watcher can push through its `tx` so the
+    /// mpsc channel's ordering - and `run_all`'s sequential processing of
+    /// it - does the synchronizing: by the time this sentinel is dequeued,
+    /// every real event the same watcher sent ahead of it is guaranteed to
+    /// already be staged. Intercepted by `run_all` before staging/emit, so
+    /// it never reaches storage, the frontend, or notifications.
+    pub(crate) fn flush_cookie(source: impl Into<String>, serial: u64) -> Self {
+        RawEvent::Dynamic(DynamicEvent {
+            source: source.into(),
+            timestamp: String::new(),
+            kind: FLUSH_COOKIE_KIND.to_string(),
+            data: serde_json::json!({ "serial": serial }),
+        })
+    }
+
+    /// If this is a [`RawEvent::flush_cookie`] sentinel, its serial.
+    pub(crate) fn as_flush_cookie(&self) -> Option<u64> {
+        match self {
+            RawEvent::Dynamic(e) if e.kind == FLUSH_COOKIE_KIND => {
+                e.data.get("serial").and_then(|v| v.as_u64())
+            }
+            _ => None,
+        }
+    }
+
+    pub fn source(&self) -> &str {
+        match self {
+            RawEvent::Checked(e) => &e.source,
+            RawEvent::Dynamic(e) => &e.source,
+        }
+    }
+
+    pub fn timestamp(&self) -> &str {
+        match self {
+            RawEvent::Checked(e) => &e.timestamp,
+            RawEvent::Dynamic(e) => &e.timestamp,
+        }
+    }
+
+    /// The `kind` column value staging rows are indexed and filtered by.
+    pub fn kind_label(&self) -> &str {
+        match self {
+            RawEvent::Checked(e) => match &e.kind {
+                CheckedKind::Commit(_) => "commit",
+            },
+            RawEvent::Dynamic(e) => &e.kind,
+        }
+    }
+
+    /// The `data` column value - the structured payload for a `Checked`
+    /// event, or the untouched JSON for a `Dynamic` one.
+    pub fn data_json(&self) -> serde_json::Value {
+        match self {
+            RawEvent::Checked(e) => match &e.kind {
+                CheckedKind::Commit(fields) => {
+                    serde_json::to_value(fields).unwrap_or(serde_json::Value::Null)
+                }
+            },
+            RawEvent::Dynamic(e) => e.data.clone(),
+        }
+    }
+
+    /// A single human-readable line for the summarizer prompt, so an LLM
+    /// doesn't have to parse raw JSON for the common, strongly-typed case.
+    pub fn render_line(&self) -> String {
+        match self {
+            RawEvent::Checked(e) => match &e.kind {
+                CheckedKind::Commit(fields) => format!(
+                    "[{}] {} commit {} in {}: {}",
+                    e.timestamp,
+                    e.source,
+                    &fields.hash[..fields.hash.len().min(8)],
+                    fields.repo,
+                    fields.subject
+                ),
+            },
+            RawEvent::Dynamic(e) => {
+                format!("[{}] {} ({}): {}", e.timestamp, e.source, e.kind, e.data)
+            }
+        }
+    }
 }
 
 #[async_trait]
 pub trait Watcher: Send {
     async fn start(&mut self, tx: tokio::sync::mpsc::Sender<RawEvent>) -> Result<(), AmberError>;
     fn stop(&mut self);
+
+    /// Stable label identifying this watcher's source, e.g. "git" or a
+    /// configured command source's name. Used to report per-source status.
+    fn name(&self) -> &str;
+
+    /// Whether this watcher is actually doing anything right now, for
+    /// `get_status` to surface per-source. `registry::start_all` parks a
+    /// watcher here even when `start()` returned `Err` (or, for `GitWatcher`,
+    /// `Ok` with nothing to watch) so a failed source still shows up in
+    /// status - this is what lets `get_status` tell that apart from one
+    /// that's actually running.
+    fn is_running(&self) -> bool;
+
+    /// Block until every filesystem event queued before this call has been
+    /// fully drained into staging. Watchers that have nothing to flush (or
+    /// no in-flight events possible) should resolve immediately.
+    async fn flush(&self) -> Result<(), AmberError> {
+        Ok(())
+    }
+
+    /// Called by `run_all`'s receive loop when a flush-barrier sentinel this
+    /// watcher emitted (see [`RawEvent::flush_cookie`]) reaches the front of
+    /// the queue - i.e. every real event this watcher queued ahead of it has
+    /// already been appended to staging. Watchers whose `flush()` never
+    /// sends a sentinel (the default) are never called.
+    fn resolve_flush_cookie(&self, _serial: u64) {}
+}
+
+/// Handle to the set of currently-running watchers, shared between the
+/// receive loop that owns them and callers (like `summarize_day`) that need
+/// to flush pending events before reading staging.
+pub type SharedWatchers = Arc<tokio::sync::Mutex<Vec<Box<dyn Watcher>>>>;
+
+/// Flush every running watcher so all events queued before this call are
+/// guaranteed to have been written to staging by the time it returns.
+pub async fn flush_all(watchers: &SharedWatchers) -> Result<(), AmberError> {
+    let guard = watchers.lock().await;
+    for watcher in guard.iter() {
+        watcher.flush().await?;
+    }
+    Ok(())
+}
+
+/// Push a flush-barrier sentinel through `tx` directly and wait for
+/// `run_all`'s receive loop to dequeue it - i.e. for every event sent on
+/// `tx` before this call to already be staged. Unlike a `Watcher`'s own
+/// `flush()`, this doesn't go through `flush_all` (there's no watcher to
+/// ask), which matters for channel-injected events with no watcher behind
+/// them - `replay()` pushes events onto `tx` directly, and
+/// `GitWatcher::flush()` only covers events the git watcher itself sent
+/// (and is a no-op when no repos are watched), so
+/// `flush_all` alone can't guarantee a replayed batch has landed in
+/// storage before `summarize_day` reads it.
+pub async fn drain_barrier(tx: &tokio::sync::mpsc::Sender<RawEvent>) -> Result<(), AmberError> {
+    static SERIAL: AtomicU64 = AtomicU64::new(0);
+    let serial = SERIAL.fetch_add(1, Ordering::SeqCst) + 1;
+
+    let (ack_tx, ack_rx) = oneshot::channel();
+    drain_waiters().lock().unwrap().insert(serial, ack_tx);
+
+    if tx
+        .send(RawEvent::flush_cookie(DRAIN_BARRIER_SOURCE, serial))
+        .await
+        .is_err()
+    {
+        drain_waiters().lock().unwrap().remove(&serial);
+        return Err(AmberError::Watcher(
+            "Event channel closed before drain barrier could be sent".into(),
+        ));
+    }
+
+    ack_rx
+        .await
+        .map_err(|_| AmberError::Watcher("Drain barrier waiter dropped".into()))
+}
+
+/// The day an event should be staged under. Every watcher's timestamp
+/// starts with a `YYYY-MM-DD` date, so this is almost always "today" for
+/// live events - but it's what lets a replayed recording land back under
+/// its original date instead of the day it happens to be re-fed.
+fn staging_date(event: &RawEvent) -> String {
+    let timestamp = event.timestamp();
+    if timestamp.len() >= 10 && timestamp.as_bytes()[4] == b'-' && timestamp.as_bytes()[7] == b'-'
+    {
+        timestamp[..10].to_string()
+    } else {
+        chrono::Local::now().format("%Y-%m-%d").to_string()
+    }
 }
 
 pub fn run_all(app_handle: tauri::AppHandle) {
     tauri::async_runtime::spawn(async move {
-        let _ = &app_handle; // keep handle alive for future use
-
         let config = match crate::config::load_or_default() {
             Ok(c) => c,
             Err(e) => {
@@ -37,30 +301,88 @@ pub fn run_all(app_handle: tauri::AppHandle) {
             }
         };
 
-        let (tx, mut rx) = tokio::sync::mpsc::channel::<RawEvent>(100);
+        let state = app_handle.state::<std::sync::Mutex<crate::AppState>>();
+        let (watchers, event_bus, tx, mut rx) = {
+            let mut state = state.lock().unwrap();
+            let rx = state
+                .take_event_rx()
+                .expect("run_all must only be called once per process");
+            (
+                state.watchers.clone(),
+                state.event_bus.clone(),
+                state.event_tx.clone(),
+                rx,
+            )
+        };
 
-        if config.sources.git.enabled {
-            let mut git_watcher = git::GitWatcher::new(config.sources.git.clone());
-            if let Err(e) = git_watcher.start(tx.clone()).await {
-                error!("Failed to start git watcher: {}", e);
-            } else {
-                info!("Git watcher started");
-            }
-            // Leak the watcher to keep it alive for the process lifetime.
-            // The debouncer and its file watches are dropped if GitWatcher is dropped.
-            std::mem::forget(git_watcher);
+        if config.streaming.enabled {
+            crate::stream::spawn_server(event_bus.clone(), config.streaming.bind_addr.clone());
         }
 
-        // Drop our copy so rx closes when all watchers drop their senders
-        drop(tx);
+        // Instantiate every enabled source and park it in the shared
+        // registry, where both this receive loop and `flush_all` can reach
+        // it. Adding a new source never touches this function. `tx` is also
+        // kept on `AppState` so a replayed recording can be fed back through
+        // this same channel later.
+        registry::start_all(
+            &config.sources,
+            tx.clone(),
+            &watchers,
+            &app_handle,
+            &config.notifications,
+        )
+        .await;
 
-        // Receive loop: serialize events and append to staging JSONL
+        // Receive loop: serialize events, append to staging, and notify
         while let Some(event) = rx.recv().await {
-            let date = chrono::Local::now().format("%Y-%m-%d").to_string();
+            // Flush-barrier sentinel: every real event its watcher sent
+            // ahead of it on this same channel has just been appended to
+            // staging above, in order, by this loop - so resolving it here
+            // (rather than in the watcher itself) is what actually
+            // guarantees `flush()` can't return early. Never staged, fanned
+            // out, or notified on.
+            if let Some(serial) = event.as_flush_cookie() {
+                if event.source() == DRAIN_BARRIER_SOURCE {
+                    if let Some(ack) = drain_waiters().lock().unwrap().remove(&serial) {
+                        let _ = ack.send(());
+                    }
+                } else {
+                    let guard = watchers.lock().await;
+                    for watcher in guard.iter() {
+                        if watcher.name() == event.source() {
+                            watcher.resolve_flush_cookie(serial);
+                        }
+                    }
+                }
+                continue;
+            }
+
+            let date = staging_date(&event);
+
+            // Fan the event out live before it's even persisted - a lagging
+            // or absent subscriber must never hold up staging writes.
+            let _ = event_bus.send(event.clone());
+            if let Err(e) = app_handle.emit("activity-event", &event) {
+                error!("Failed to emit activity event: {}", e);
+            }
+
             match serde_json::to_string(&event) {
                 Ok(json) => {
                     if let Err(e) = crate::storage::append_staging_event(&date, &json).await {
                         error!("Failed to append staging event: {}", e);
+                    } else if let RawEvent::Checked(CheckedEvent {
+                        kind: CheckedKind::Commit(fields),
+                        ..
+                    }) = &event
+                    {
+                        crate::notifier::notify_new_commit(
+                            &app_handle,
+                            &config.notifications,
+                            &fields.repo,
+                            &fields.hash,
+                            &fields.subject,
+                        )
+                        .await;
                     }
                 }
                 Err(e) => {
@@ -0,0 +1,75 @@
+use log::{error, info};
+
+use crate::config::{NotificationsConfig, SourcesConfig};
+use crate::watchers::{
+    command::CommandWatcher, git::GitWatcher, webhook::WebhookWatcher, RawEvent, SharedWatchers,
+    Watcher,
+};
+
+/// Instantiate every enabled source from `SourcesConfig` behind `Box<dyn
+/// Watcher>`, start it, and park it in `watchers` so it fans its `RawEvent`s
+/// into `tx` for the process lifetime. Adding a new activity source only
+/// means adding a `Watcher` impl and a branch here - `run_all`, the
+/// scheduler, and the summarizer are all source-agnostic.
+pub async fn start_all(
+    config: &SourcesConfig,
+    tx: tokio::sync::mpsc::Sender<RawEvent>,
+    watchers: &SharedWatchers,
+    app_handle: &tauri::AppHandle,
+    notifications: &NotificationsConfig,
+) {
+    if config.git.enabled {
+        let mut watcher = GitWatcher::new(config.git.clone());
+        match watcher.start(tx.clone()).await {
+            Ok(()) => info!("Git watcher started"),
+            Err(e) => {
+                error!("Failed to start git watcher: {}", e);
+                crate::notifier::notify_watcher_error(app_handle, notifications, "git", &e.to_string())
+                    .await;
+            }
+        }
+        watchers.lock().await.push(Box::new(watcher));
+    }
+
+    for command_config in &config.commands {
+        if !command_config.enabled {
+            continue;
+        }
+        let mut watcher = CommandWatcher::new(command_config.clone());
+        match watcher.start(tx.clone()).await {
+            Ok(()) => info!("Command watcher '{}' started", command_config.name),
+            Err(e) => {
+                error!(
+                    "Failed to start command watcher '{}': {}",
+                    command_config.name, e
+                );
+                crate::notifier::notify_watcher_error(
+                    app_handle,
+                    notifications,
+                    &command_config.name,
+                    &e.to_string(),
+                )
+                .await;
+            }
+        }
+        watchers.lock().await.push(Box::new(watcher));
+    }
+
+    if config.webhook.enabled {
+        let mut watcher = WebhookWatcher::new(config.webhook.clone());
+        match watcher.start(tx.clone()).await {
+            Ok(()) => info!("Webhook watcher started on {}", config.webhook.bind_addr),
+            Err(e) => {
+                error!("Failed to start webhook watcher: {}", e);
+                crate::notifier::notify_watcher_error(
+                    app_handle,
+                    notifications,
+                    "webhook",
+                    &e.to_string(),
+                )
+                .await;
+            }
+        }
+        watchers.lock().await.push(Box::new(watcher));
+    }
+}
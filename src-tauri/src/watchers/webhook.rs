@@ -0,0 +1,127 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::post;
+use axum::Router;
+use log::{error, info, warn};
+use tokio::sync::oneshot;
+
+use crate::config::WebhookSourceConfig;
+use crate::error::AmberError;
+use crate::watchers::{RawEvent, Watcher};
+
+#[derive(Clone)]
+struct WebhookState {
+    tx: tokio::sync::mpsc::Sender<RawEvent>,
+    shared_secret: String,
+}
+
+/// Ingests events over HTTP instead of watching the filesystem, so external
+/// tools (CI, other scripts) can push activity into Amber without a native
+/// `Watcher` impl of their own.
+pub struct WebhookWatcher {
+    config: WebhookSourceConfig,
+    shutdown_tx: Option<oneshot::Sender<()>>,
+    running: Arc<AtomicBool>,
+}
+
+impl WebhookWatcher {
+    pub fn new(config: WebhookSourceConfig) -> Self {
+        Self {
+            config,
+            shutdown_tx: None,
+            running: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+async fn ingest(
+    State(state): State<WebhookState>,
+    headers: HeaderMap,
+    body: axum::body::Bytes,
+) -> StatusCode {
+    let provided = headers
+        .get("x-amber-secret")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    if provided != state.shared_secret {
+        return StatusCode::UNAUTHORIZED;
+    }
+
+    match serde_json::from_slice::<RawEvent>(&body) {
+        Ok(event) => {
+            if state.tx.send(event).await.is_err() {
+                return StatusCode::SERVICE_UNAVAILABLE;
+            }
+            StatusCode::ACCEPTED
+        }
+        Err(e) => {
+            warn!("Rejected malformed webhook payload: {}", e);
+            StatusCode::BAD_REQUEST
+        }
+    }
+}
+
+#[async_trait]
+impl Watcher for WebhookWatcher {
+    async fn start(&mut self, tx: tokio::sync::mpsc::Sender<RawEvent>) -> Result<(), AmberError> {
+        if self.config.shared_secret.is_empty() {
+            return Err(AmberError::Watcher(
+                "sources.webhook.shared_secret must be set before the webhook watcher can start"
+                    .into(),
+            ));
+        }
+
+        let state = WebhookState {
+            tx,
+            shared_secret: self.config.shared_secret.clone(),
+        };
+        let app = Router::new()
+            .route("/events", post(ingest))
+            .with_state(state);
+
+        let listener = tokio::net::TcpListener::bind(&self.config.bind_addr)
+            .await
+            .map_err(|e| {
+                AmberError::Watcher(format!(
+                    "Failed to bind webhook listener on {}: {}",
+                    self.config.bind_addr, e
+                ))
+            })?;
+
+        info!("Webhook watcher listening on {}", self.config.bind_addr);
+
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+        self.shutdown_tx = Some(shutdown_tx);
+        self.running.store(true, Ordering::SeqCst);
+
+        tauri::async_runtime::spawn(async move {
+            let server = axum::serve(listener, app).with_graceful_shutdown(async {
+                let _ = shutdown_rx.await;
+            });
+            if let Err(e) = server.await {
+                error!("Webhook watcher server error: {}", e);
+            }
+        });
+
+        Ok(())
+    }
+
+    fn stop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+        if let Some(tx) = self.shutdown_tx.take() {
+            let _ = tx.send(());
+        }
+    }
+
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+}